@@ -0,0 +1,15 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 26/7/26
+******************************************************************************/
+
+//! `orderbook-rs`: a price-time priority limit order book engine.
+//!
+//! The crate exposes [`OrderBook`] as its main entry point; everything
+//! else (order types, cancellation helpers, the price-level cache, ...)
+//! lives under the [`orderbook`] module.
+
+pub mod orderbook;
+
+pub use orderbook::{OrderBook, STPMode};
@@ -0,0 +1,49 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 26/7/26
+******************************************************************************/
+
+//! Optional per-market tick/lot/min-size validation, borrowed from
+//! DeepBook's `Book` (`tick_size`, `lot_size`, `min_size`) and Mango's
+//! per-market lot sizing.
+
+use crate::orderbook::error::OrderBookError;
+
+/// Validation rules for a single market. When not configured, an
+/// [`OrderBook`](crate::OrderBook) accepts any price/quantity exactly as
+/// it does today.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarketConstraints {
+    /// `price` must be an integer multiple of this.
+    pub tick_size: u128,
+    /// `quantity` must be an integer multiple of this.
+    pub lot_size: u128,
+    /// `quantity` must be at least this.
+    pub min_size: u128,
+}
+
+impl MarketConstraints {
+    /// Validates `price` and `quantity` against this market's rules.
+    pub fn validate(&self, price: u128, quantity: u128) -> Result<(), OrderBookError> {
+        if self.tick_size > 0 && price % self.tick_size != 0 {
+            return Err(OrderBookError::InvalidTickSize {
+                price,
+                tick_size: self.tick_size,
+            });
+        }
+        if self.lot_size > 0 && quantity % self.lot_size != 0 {
+            return Err(OrderBookError::InvalidLotSize {
+                quantity,
+                lot_size: self.lot_size,
+            });
+        }
+        if quantity < self.min_size {
+            return Err(OrderBookError::OrderBelowMinimumSize {
+                quantity,
+                min_size: self.min_size,
+            });
+        }
+        Ok(())
+    }
+}
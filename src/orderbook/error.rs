@@ -0,0 +1,55 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 26/7/26
+******************************************************************************/
+
+use pricelevel::OrderId;
+use std::fmt;
+
+/// Errors returned while placing or cancelling orders on an [`OrderBook`](crate::OrderBook).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderBookError {
+    /// `price` is zero, which is never a valid resting price.
+    InvalidPrice,
+    /// `quantity` is zero.
+    InvalidQuantity,
+    /// No resting order with this id could be found.
+    OrderNotFound(OrderId),
+    /// A post-only order would have crossed the book on entry.
+    PostOnlyWouldCross,
+    /// `price` is not an integer multiple of the market's `tick_size`.
+    InvalidTickSize { price: u128, tick_size: u128 },
+    /// `quantity` is not an integer multiple of the market's `lot_size`.
+    InvalidLotSize { quantity: u128, lot_size: u128 },
+    /// `quantity` is below the market's configured `min_size`.
+    OrderBelowMinimumSize { quantity: u128, min_size: u128 },
+}
+
+impl fmt::Display for OrderBookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPrice => write!(f, "order price must be greater than zero"),
+            Self::InvalidQuantity => write!(f, "order quantity must be greater than zero"),
+            Self::OrderNotFound(id) => write!(f, "order {id:?} not found"),
+            Self::PostOnlyWouldCross => write!(f, "post-only order would have crossed the book"),
+            Self::InvalidTickSize { price, tick_size } => {
+                write!(f, "price {price} is not a multiple of tick size {tick_size}")
+            }
+            Self::InvalidLotSize { quantity, lot_size } => {
+                write!(
+                    f,
+                    "quantity {quantity} is not a multiple of lot size {lot_size}"
+                )
+            }
+            Self::OrderBelowMinimumSize { quantity, min_size } => {
+                write!(
+                    f,
+                    "quantity {quantity} is below the minimum order size {min_size}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for OrderBookError {}
@@ -0,0 +1,80 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 26/7/26
+******************************************************************************/
+
+//! Opt-in, strongly-typed order event stream, inspired by lobster's
+//! `OrderEvent` (`Placed`/`Unfilled`/`PartiallyFilled`/`Filled`) and
+//! Mango's event queue.
+//!
+//! Nothing is emitted until at least one [`OrderBook::subscribe`](crate::OrderBook::subscribe)
+//! call has registered a receiver, so books that don't care about
+//! events pay no extra cost.
+
+use crossbeam::channel::{self, Receiver, Sender};
+use pricelevel::{Hash32, OrderId};
+
+/// Why a single order was cancelled out-of-band (i.e. not as part of a
+/// batch cancel, which is reported as [`BookEvent::MassCancelled`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CancelReason {
+    /// Removed by self-trade prevention.
+    SelfTrade,
+    /// The remainder of a market order that could not be filled; market
+    /// orders never rest, so any shortfall is cancelled instead of queued.
+    Unfilled,
+}
+
+/// A single book event, emitted to every subscriber registered via
+/// [`OrderBook::subscribe`](crate::OrderBook::subscribe).
+#[derive(Debug, Clone)]
+pub enum BookEvent {
+    /// A new order started resting on the book.
+    Placed { id: OrderId, user: Option<Hash32> },
+    /// An order matched against part of its quantity and is still resting.
+    PartiallyFilled {
+        id: OrderId,
+        user: Option<Hash32>,
+        filled_qty: u128,
+        remaining: u128,
+    },
+    /// An order matched its entire quantity, or a market order traded
+    /// some quantity (any shortfall follows as a `Cancelled` event with
+    /// [`CancelReason::Unfilled`]).
+    Filled { id: OrderId, user: Option<Hash32> },
+    /// A single order (a resting maker, or a market order's unfilled
+    /// remainder) was cancelled outside of a batch.
+    Cancelled {
+        id: OrderId,
+        user: Option<Hash32>,
+        reason: CancelReason,
+    },
+    /// A batch of orders were removed together by one of the mass
+    /// cancel operations; corresponds one-to-one with the
+    /// [`MassCancelResult`](crate::orderbook::mass_cancel::MassCancelResult)
+    /// the same call returned.
+    MassCancelled { ids: Vec<OrderId> },
+}
+
+/// Holds every live subscriber for a book, pruning disconnected ones as
+/// it sends.
+#[derive(Debug, Default)]
+pub(crate) struct EventBus {
+    subscribers: Vec<Sender<BookEvent>>,
+}
+
+impl EventBus {
+    pub fn subscribe(&mut self) -> Receiver<BookEvent> {
+        let (tx, rx) = channel::unbounded();
+        self.subscribers.push(tx);
+        rx
+    }
+
+    pub fn emit(&mut self, event: BookEvent) {
+        if self.subscribers.is_empty() {
+            return;
+        }
+        self.subscribers.retain(|tx| tx.send(event.clone()).is_ok());
+    }
+}
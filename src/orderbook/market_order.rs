@@ -0,0 +1,21 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 26/7/26
+******************************************************************************/
+
+//! Immediate (IOC-like) market order execution: sweep the opposite side
+//! and never rest, modeled on the "market" order type most CLOBs expose
+//! alongside resting limit orders.
+
+/// Result of [`OrderBook::execute_market_order`](crate::OrderBook::execute_market_order).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MarketOrderResult {
+    /// Total quantity filled.
+    pub filled_qty: u128,
+    /// Volume-weighted average fill price, or `None` if nothing filled.
+    pub avg_fill_price: Option<u128>,
+    /// Quantity that could not be filled. A market order never rests,
+    /// so this portion is simply not executed.
+    pub unfilled_qty: u128,
+}
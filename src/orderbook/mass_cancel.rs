@@ -0,0 +1,80 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 26/7/26
+******************************************************************************/
+
+//! Result type shared by every "mass cancel" style operation on
+//! [`OrderBook`](crate::OrderBook) (`cancel_all_orders`, `cancel_orders_by_side`,
+//! `cancel_orders_by_user`, `cancel_orders_by_price_range`, ...).
+
+use pricelevel::OrderId;
+use std::fmt;
+
+/// Outcome of a bulk cancellation.
+///
+/// Carries every id that was actually removed from the book so callers
+/// can reconcile their own order tracking without re-querying it. When
+/// produced by a `_limited` variant, `skipped_count` and
+/// `more_remaining` report how much of the matching set was left
+/// untouched by the per-call cap.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MassCancelResult {
+    cancelled_order_ids: Vec<OrderId>,
+    skipped_count: usize,
+    more_remaining: bool,
+}
+
+impl MassCancelResult {
+    /// Number of orders removed from the book.
+    pub fn cancelled_count(&self) -> usize {
+        self.cancelled_order_ids.len()
+    }
+
+    /// Ids of every order removed from the book.
+    pub fn cancelled_order_ids(&self) -> &[OrderId] {
+        &self.cancelled_order_ids
+    }
+
+    /// `true` if nothing was cancelled.
+    pub fn is_empty(&self) -> bool {
+        self.cancelled_order_ids.is_empty()
+    }
+
+    /// Number of orders that matched the cancellation criteria but were
+    /// left resting because the call's `max` cap was reached. Always
+    /// zero for the unbounded cancel methods.
+    pub fn skipped_count(&self) -> usize {
+        self.skipped_count
+    }
+
+    /// `true` if the cap was hit and a further call (e.g. in a loop)
+    /// would cancel more orders.
+    pub fn more_remaining(&self) -> bool {
+        self.more_remaining
+    }
+
+    pub(crate) fn push(&mut self, id: OrderId) {
+        self.cancelled_order_ids.push(id);
+    }
+
+    pub(crate) fn extend(&mut self, ids: impl IntoIterator<Item = OrderId>) {
+        self.cancelled_order_ids.extend(ids);
+    }
+
+    pub(crate) fn set_skipped(&mut self, skipped_count: usize) {
+        self.skipped_count = skipped_count;
+        self.more_remaining = skipped_count > 0;
+    }
+}
+
+impl fmt::Display for MassCancelResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cancelled {} order(s), {} skipped",
+            self.cancelled_count(),
+            self.skipped_count
+        )
+    }
+}
@@ -0,0 +1,1018 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 26/7/26
+******************************************************************************/
+
+//! Price-time priority limit order book.
+//!
+//! [`OrderBook`] keeps resting orders on two sides (bids and asks) in a
+//! fixed price tree, plus a separate interleaved set of oracle-pegged
+//! orders (see [`oracle_peg`]) whose price tracks an external
+//! reference. A lock-free [`cache::PriceLevelCache`] caches the current
+//! best bid/ask for cheap reads.
+
+pub mod cache;
+pub mod constraints;
+pub mod error;
+pub mod events;
+pub mod market_order;
+pub mod mass_cancel;
+pub mod oracle_peg;
+pub(crate) mod order;
+
+pub use constraints::MarketConstraints;
+pub use error::OrderBookError;
+pub use events::{BookEvent, CancelReason};
+pub use market_order::MarketOrderResult;
+pub use mass_cancel::MassCancelResult;
+
+use cache::PriceLevelCache;
+use crossbeam::channel::Receiver;
+use events::EventBus;
+use oracle_peg::{OraclePeggedBook, OraclePeggedOrder};
+use order::RestingOrder;
+
+use crossbeam::atomic::AtomicCell;
+use pricelevel::{Hash32, OrderId, Side, TimeInForce};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::RwLock;
+
+/// Self-trade prevention mode, applied whenever an incoming order would
+/// otherwise match against a resting order from the same user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum STPMode {
+    /// Self-trades are allowed.
+    #[default]
+    None,
+    /// Cancel the resting (maker) order and keep matching the taker.
+    CancelMaker,
+    /// Cancel the incoming (taker) order as soon as a self-trade would occur.
+    CancelTaker,
+    /// Cancel both the resting and incoming orders.
+    CancelBoth,
+}
+
+/// Where a resting order lives, so it can be found and removed
+/// without walking every price level.
+#[derive(Debug, Clone, Copy)]
+enum OrderLocation {
+    Fixed { side: Side, price: u128 },
+    Pegged { side: Side },
+}
+
+type PriceLevels<T> = BTreeMap<u128, VecDeque<RestingOrder<T>>>;
+
+/// Outcome of matching an incoming order against the opposite side.
+#[derive(Debug, Clone, Copy, Default)]
+struct MatchOutcome {
+    /// Quantity left unfilled when matching stopped.
+    remaining: u128,
+    /// Total quantity filled.
+    filled: u128,
+    /// Sum of `price * fill` across every maker, used to compute the
+    /// taker's volume-weighted average fill price.
+    notional: u128,
+}
+
+/// What to do with a maker order that shares a user with the taker.
+enum StpOutcome {
+    /// No self-trade (different users, or no user on one side): fill normally.
+    Allow,
+    /// `CancelMaker`: the maker was cancelled; move on to the next one.
+    SkipMaker,
+    /// `CancelTaker`: the maker is untouched; the taker's sweep stops here.
+    AbortKeepMaker,
+    /// `CancelBoth`: the maker was cancelled and the taker's sweep stops here.
+    AbortCancelMaker,
+}
+
+/// A price-time priority limit order book for a single symbol.
+///
+/// `T` is an arbitrary piece of metadata the caller may attach to each
+/// order; the book never inspects it.
+#[derive(Debug)]
+pub struct OrderBook<T> {
+    symbol: String,
+    bids: RwLock<PriceLevels<T>>,
+    asks: RwLock<PriceLevels<T>>,
+    pegged: RwLock<OraclePeggedBook>,
+    locations: RwLock<HashMap<OrderId, OrderLocation>>,
+    cache: PriceLevelCache,
+    /// `None` until the first [`Self::set_reference_price`] call, so a
+    /// pegged order added before any reference exists is parked rather
+    /// than priced off of a guessed `0`.
+    reference_price: AtomicCell<Option<u128>>,
+    stp_mode: AtomicCell<STPMode>,
+    constraints: Option<MarketConstraints>,
+    /// Timestamp of the last [`Self::cancel_expired_orders`] sweep,
+    /// used only by the timestamp-free reads ([`Self::best_bid`],
+    /// [`Self::best_ask`]) to skip price levels that are nothing but
+    /// expired `Gtd` orders. Order entry and matching take their own
+    /// `now_ts` from the caller instead of relying on this (see
+    /// [`Self::match_incoming`]), so they stay accurate even between
+    /// sweeps; only these cache-backed, timestamp-free getters fall
+    /// back to "as of the last sweep".
+    clock: AtomicCell<u64>,
+    events: RwLock<EventBus>,
+}
+
+impl<T> OrderBook<T> {
+    /// Creates an empty order book for `symbol`, with no price/quantity
+    /// validation beyond `price != 0` and `quantity != 0`.
+    pub fn new(symbol: impl Into<String>) -> Self {
+        Self::new_with_constraints_impl(symbol, None)
+    }
+
+    /// Creates an empty order book for `symbol` that rejects orders
+    /// violating `constraints` (tick size, lot size, minimum size) from
+    /// `add_limit_order`, `add_iceberg_order`, and `add_post_only_order`.
+    pub fn new_with_constraints(symbol: impl Into<String>, constraints: MarketConstraints) -> Self {
+        Self::new_with_constraints_impl(symbol, Some(constraints))
+    }
+
+    fn new_with_constraints_impl(symbol: impl Into<String>, constraints: Option<MarketConstraints>) -> Self {
+        Self {
+            symbol: symbol.into(),
+            bids: RwLock::new(BTreeMap::new()),
+            asks: RwLock::new(BTreeMap::new()),
+            pegged: RwLock::new(OraclePeggedBook::default()),
+            locations: RwLock::new(HashMap::new()),
+            cache: PriceLevelCache::new(),
+            reference_price: AtomicCell::new(None),
+            stp_mode: AtomicCell::new(STPMode::None),
+            constraints,
+            clock: AtomicCell::new(0),
+            events: RwLock::new(EventBus::default()),
+        }
+    }
+
+    /// Subscribes to this book's event stream. Every subsequent
+    /// `Placed`, `PartiallyFilled`, `Filled`, `Cancelled`, and
+    /// `MassCancelled` event is sent to the returned receiver until it
+    /// is dropped.
+    pub fn subscribe(&self) -> Receiver<BookEvent> {
+        self.events.write().unwrap().subscribe()
+    }
+
+    fn emit(&self, event: BookEvent) {
+        self.events.write().unwrap().emit(event);
+    }
+
+    /// Emits a single [`BookEvent::MassCancelled`] batch matching
+    /// `result` one-to-one, unless nothing was actually cancelled.
+    fn emit_mass_cancelled(&self, result: &MassCancelResult) {
+        if !result.is_empty() {
+            self.emit(BookEvent::MassCancelled {
+                ids: result.cancelled_order_ids().to_vec(),
+            });
+        }
+    }
+
+    /// `true` if `time_in_force` is a `Gtd` order whose expiry has
+    /// passed as of `now_ts`.
+    fn is_expired(time_in_force: TimeInForce, now_ts: u64) -> bool {
+        order::is_expired(time_in_force, now_ts)
+    }
+
+    /// The symbol this book was created for.
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// Sets the self-trade prevention mode applied to future matches.
+    pub fn set_stp_mode(&mut self, mode: STPMode) {
+        self.stp_mode.store(mode);
+    }
+
+    /// The current best bid, if any resting (fixed or pegged) buy order exists.
+    pub fn best_bid(&self) -> Option<u128> {
+        if let Some(price) = self.cache.get_cached_best_bid() {
+            return Some(price);
+        }
+        self.recompute_cache();
+        self.cache.get_cached_best_bid()
+    }
+
+    /// The current best ask, if any resting (fixed or pegged) sell order exists.
+    pub fn best_ask(&self) -> Option<u128> {
+        if let Some(price) = self.cache.get_cached_best_ask() {
+            return Some(price);
+        }
+        self.recompute_cache();
+        self.cache.get_cached_best_ask()
+    }
+
+    /// Best price on `side` across the fixed price tree, skipping over
+    /// price levels that contain nothing except expired `Gtd` orders
+    /// (those are left in place for [`Self::cancel_expired_orders`] to
+    /// sweep up later).
+    fn best_valid_fixed_price(levels: &PriceLevels<T>, side: Side, now_ts: u64) -> Option<u128> {
+        let has_valid = |queue: &VecDeque<RestingOrder<T>>| {
+            queue.iter().any(|o| !Self::is_expired(o.time_in_force, now_ts))
+        };
+        match side {
+            Side::Buy => levels.iter().rev().find(|(_, q)| has_valid(q)).map(|(p, _)| *p),
+            Side::Sell => levels.iter().find(|(_, q)| has_valid(q)).map(|(p, _)| *p),
+        }
+    }
+
+    /// Removes and returns the first non-expired order in `queue`,
+    /// leaving any expired orders ahead of it untouched.
+    fn take_first_valid(queue: &mut VecDeque<RestingOrder<T>>, now_ts: u64) -> Option<RestingOrder<T>> {
+        let idx = queue.iter().position(|o| !Self::is_expired(o.time_in_force, now_ts))?;
+        queue.remove(idx)
+    }
+
+    /// Best price on `side` as of `now_ts`, skipping price levels and
+    /// pegged orders that are nothing but expired `Gtd` orders — the
+    /// same filter [`Self::match_incoming`] applies, so a price
+    /// reported here is always one a taker could actually cross.
+    fn best_of_side_valid(&self, side: Side, now_ts: u64) -> Option<u128> {
+        let fixed = match side {
+            Side::Buy => Self::best_valid_fixed_price(&self.bids.read().unwrap(), side, now_ts),
+            Side::Sell => Self::best_valid_fixed_price(&self.asks.read().unwrap(), side, now_ts),
+        };
+        let pegged = self.pegged.read().unwrap().best_valid(side, now_ts).map(|o| {
+            o.effective_price()
+                .expect("`best_valid` only returns priced orders")
+        });
+        match (fixed, pegged) {
+            (None, None) => None,
+            (Some(f), None) => Some(f),
+            (None, Some(p)) => Some(p),
+            (Some(f), Some(p)) => Some(match side {
+                Side::Buy => f.max(p),
+                Side::Sell => f.min(p),
+            }),
+        }
+    }
+
+    fn best_of_side(&self, side: Side) -> Option<u128> {
+        self.best_of_side_valid(side, self.clock.load())
+    }
+
+    fn recompute_cache(&self) {
+        let best_bid = self.best_of_side(Side::Buy);
+        let best_ask = self.best_of_side(Side::Sell);
+        self.cache.update_best_prices(best_bid, best_ask);
+    }
+
+    // -----------------------------------------------------------------
+    // Order entry
+    // -----------------------------------------------------------------
+
+    /// Places a limit order with no associated user.
+    ///
+    /// `now_ts` is the caller's current time, used to skip over any
+    /// resting `Gtd` order whose expiry has passed even if it hasn't
+    /// been swept yet by [`Self::cancel_expired_orders`]; see
+    /// [`Self::match_incoming`].
+    pub fn add_limit_order(
+        &self,
+        id: OrderId,
+        price: u128,
+        quantity: u128,
+        side: Side,
+        time_in_force: TimeInForce,
+        now_ts: u64,
+        metadata: Option<T>,
+    ) -> Result<(), OrderBookError> {
+        self.place_limit_order(id, price, quantity, side, time_in_force, now_ts, None, metadata)
+    }
+
+    /// Places a limit order attributed to `user`, enabling per-user
+    /// self-trade prevention and mass cancellation. See
+    /// [`Self::add_limit_order`] for the meaning of `now_ts`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_limit_order_with_user(
+        &self,
+        id: OrderId,
+        price: u128,
+        quantity: u128,
+        side: Side,
+        time_in_force: TimeInForce,
+        now_ts: u64,
+        user: Hash32,
+        metadata: Option<T>,
+    ) -> Result<(), OrderBookError> {
+        self.place_limit_order(
+            id,
+            price,
+            quantity,
+            side,
+            time_in_force,
+            now_ts,
+            Some(user),
+            metadata,
+        )
+    }
+
+    /// Places an iceberg order of `total_quantity` at `price`.
+    ///
+    /// `peak_quantity` is accepted but not used: the full quantity rests
+    /// at `price` as a single order and is matched as one. See
+    /// [`Self::add_limit_order`] for the meaning of `now_ts`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_iceberg_order(
+        &self,
+        id: OrderId,
+        price: u128,
+        peak_quantity: u128,
+        total_quantity: u128,
+        side: Side,
+        time_in_force: TimeInForce,
+        now_ts: u64,
+        metadata: Option<T>,
+    ) -> Result<(), OrderBookError> {
+        let _ = peak_quantity;
+        self.place_limit_order(id, price, total_quantity, side, time_in_force, now_ts, None, metadata)
+    }
+
+    /// Places a post-only order: rejected with
+    /// [`OrderBookError::PostOnlyWouldCross`] instead of matching if it
+    /// would cross the book on entry. The crossing check is evaluated
+    /// as of `now_ts` (see [`Self::add_limit_order`]), so a price level
+    /// that is nothing but expired `Gtd` orders never causes a
+    /// rejection.
+    pub fn add_post_only_order(
+        &self,
+        id: OrderId,
+        price: u128,
+        quantity: u128,
+        side: Side,
+        time_in_force: TimeInForce,
+        now_ts: u64,
+        metadata: Option<T>,
+    ) -> Result<(), OrderBookError> {
+        let would_cross = match side {
+            Side::Buy => self
+                .best_of_side_valid(Side::Sell, now_ts)
+                .is_some_and(|ask| price >= ask),
+            Side::Sell => self
+                .best_of_side_valid(Side::Buy, now_ts)
+                .is_some_and(|bid| price <= bid),
+        };
+        if would_cross {
+            return Err(OrderBookError::PostOnlyWouldCross);
+        }
+        self.place_limit_order(id, price, quantity, side, time_in_force, now_ts, None, metadata)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn place_limit_order(
+        &self,
+        id: OrderId,
+        price: u128,
+        quantity: u128,
+        side: Side,
+        time_in_force: TimeInForce,
+        now_ts: u64,
+        user: Option<Hash32>,
+        metadata: Option<T>,
+    ) -> Result<(), OrderBookError> {
+        if price == 0 {
+            return Err(OrderBookError::InvalidPrice);
+        }
+        if quantity == 0 {
+            return Err(OrderBookError::InvalidQuantity);
+        }
+        if let Some(constraints) = &self.constraints {
+            constraints.validate(price, quantity)?;
+        }
+
+        let outcome = self.match_incoming(side, Some(price), quantity, user, now_ts);
+        let remaining = outcome.remaining;
+        if remaining > 0 {
+            self.rest_fixed_order(RestingOrder {
+                id,
+                price,
+                quantity: remaining,
+                side,
+                time_in_force,
+                user,
+                metadata,
+            });
+            self.emit(BookEvent::Placed { id, user });
+        }
+        if outcome.filled > 0 {
+            if remaining == 0 {
+                self.emit(BookEvent::Filled { id, user });
+            } else {
+                self.emit(BookEvent::PartiallyFilled {
+                    id,
+                    user,
+                    filled_qty: outcome.filled,
+                    remaining,
+                });
+            }
+        }
+        self.recompute_cache();
+        Ok(())
+    }
+
+    fn rest_fixed_order(&self, order: RestingOrder<T>) {
+        let levels = match order.side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+        let (side, price, id) = (order.side, order.price, order.id);
+        levels.write().unwrap().entry(price).or_default().push_back(order);
+        self.locations
+            .write()
+            .unwrap()
+            .insert(id, OrderLocation::Fixed { side, price });
+    }
+
+    /// Merges the fixed price tree and the pegged order set by
+    /// effective price and walks them in price-time priority until
+    /// `remaining` is filled, the book is exhausted, matching stops
+    /// crossing `taker_limit`, or `STPMode::CancelTaker`/`CancelBoth`
+    /// aborts the sweep on a self-trade. At equal price a fixed order
+    /// trades before a pegged one; within each set, orders at the same
+    /// price trade in arrival order.
+    ///
+    /// `now_ts` is the caller's current time: any resting `Gtd` order
+    /// that has already expired as of `now_ts` is skipped even if
+    /// [`Self::cancel_expired_orders`] hasn't swept it up yet, so a
+    /// taker never crosses an order that should already be dead.
+    fn match_incoming(
+        &self,
+        taker_side: Side,
+        taker_limit: Option<u128>,
+        mut remaining: u128,
+        taker_user: Option<Hash32>,
+        now_ts: u64,
+    ) -> MatchOutcome {
+        let opposite = taker_side.opposite();
+        let mut fixed = match opposite {
+            Side::Buy => self.bids.write().unwrap(),
+            Side::Sell => self.asks.write().unwrap(),
+        };
+        let mut pegged = self.pegged.write().unwrap();
+        let mut locations = self.locations.write().unwrap();
+        let initial = remaining;
+        let mut notional = 0u128;
+
+        'matching: while remaining > 0 {
+            let fixed_best = Self::best_valid_fixed_price(&fixed, opposite, now_ts);
+            let pegged_best = pegged.best_valid(opposite, now_ts).map(|o| {
+                o.effective_price()
+                    .expect("`best_valid` only returns priced orders")
+            });
+            let use_pegged = match (fixed_best, pegged_best) {
+                (None, None) => break,
+                (Some(_), None) => false,
+                (None, Some(_)) => true,
+                (Some(f), Some(p)) => match opposite {
+                    Side::Buy => p > f,
+                    Side::Sell => p < f,
+                },
+            };
+            let price = if use_pegged {
+                pegged_best.unwrap()
+            } else {
+                fixed_best.unwrap()
+            };
+
+            let crosses = match taker_limit {
+                None => true,
+                Some(limit) => match taker_side {
+                    Side::Buy => price <= limit,
+                    Side::Sell => price >= limit,
+                },
+            };
+            if !crosses {
+                break;
+            }
+
+            if use_pegged {
+                let maker = pegged.pop_best_valid(opposite, now_ts).expect("checked above");
+                let mut maker = match self.apply_stp(taker_user, maker.user, &mut locations, maker.id) {
+                    StpOutcome::Allow => maker,
+                    StpOutcome::SkipMaker => continue 'matching,
+                    StpOutcome::AbortKeepMaker => {
+                        pegged.put_back(maker);
+                        break 'matching;
+                    }
+                    StpOutcome::AbortCancelMaker => break 'matching,
+                };
+                let fill = remaining.min(maker.quantity);
+                remaining -= fill;
+                notional += price * fill;
+                maker.quantity -= fill;
+                if maker.quantity > 0 {
+                    self.emit(BookEvent::PartiallyFilled {
+                        id: maker.id,
+                        user: maker.user,
+                        filled_qty: fill,
+                        remaining: maker.quantity,
+                    });
+                    pegged.put_back(maker);
+                } else {
+                    self.emit(BookEvent::Filled {
+                        id: maker.id,
+                        user: maker.user,
+                    });
+                    locations.remove(&maker.id);
+                }
+            } else {
+                let mut level = fixed.remove(&price).expect("checked above");
+                let Some(maker) = Self::take_first_valid(&mut level, now_ts) else {
+                    fixed.insert(price, level);
+                    continue;
+                };
+                let mut maker = match self.apply_stp(taker_user, maker.user, &mut locations, maker.id) {
+                    StpOutcome::Allow => {
+                        if !level.is_empty() {
+                            fixed.insert(price, level);
+                        }
+                        maker
+                    }
+                    StpOutcome::SkipMaker => {
+                        if !level.is_empty() {
+                            fixed.insert(price, level);
+                        }
+                        continue 'matching;
+                    }
+                    StpOutcome::AbortKeepMaker => {
+                        level.push_front(maker);
+                        fixed.insert(price, level);
+                        break 'matching;
+                    }
+                    StpOutcome::AbortCancelMaker => {
+                        if !level.is_empty() {
+                            fixed.insert(price, level);
+                        }
+                        break 'matching;
+                    }
+                };
+                let fill = remaining.min(maker.quantity);
+                remaining -= fill;
+                notional += price * fill;
+                maker.quantity -= fill;
+                if maker.quantity > 0 {
+                    self.emit(BookEvent::PartiallyFilled {
+                        id: maker.id,
+                        user: maker.user,
+                        filled_qty: fill,
+                        remaining: maker.quantity,
+                    });
+                    fixed.entry(price).or_default().push_front(maker);
+                } else {
+                    self.emit(BookEvent::Filled {
+                        id: maker.id,
+                        user: maker.user,
+                    });
+                    locations.remove(&maker.id);
+                }
+            }
+        }
+
+        MatchOutcome {
+            remaining,
+            filled: initial - remaining,
+            notional,
+        }
+    }
+
+    /// Applies `self.stp_mode` when taker and maker share a user.
+    fn apply_stp(
+        &self,
+        taker_user: Option<Hash32>,
+        maker_user: Option<Hash32>,
+        locations: &mut HashMap<OrderId, OrderLocation>,
+        maker_id: OrderId,
+    ) -> StpOutcome {
+        let (Some(t), Some(m)) = (taker_user, maker_user) else {
+            return StpOutcome::Allow;
+        };
+        if t != m {
+            return StpOutcome::Allow;
+        }
+        match self.stp_mode.load() {
+            STPMode::None => StpOutcome::Allow,
+            STPMode::CancelTaker => StpOutcome::AbortKeepMaker,
+            STPMode::CancelMaker => {
+                locations.remove(&maker_id);
+                self.emit(BookEvent::Cancelled {
+                    id: maker_id,
+                    user: maker_user,
+                    reason: CancelReason::SelfTrade,
+                });
+                StpOutcome::SkipMaker
+            }
+            STPMode::CancelBoth => {
+                locations.remove(&maker_id);
+                self.emit(BookEvent::Cancelled {
+                    id: maker_id,
+                    user: maker_user,
+                    reason: CancelReason::SelfTrade,
+                });
+                StpOutcome::AbortCancelMaker
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------
+    // Oracle-pegged orders
+    // -----------------------------------------------------------------
+
+    /// Sets the reference price used to price oracle-pegged orders,
+    /// re-sorting every pegged order against the fixed book and
+    /// invalidating the best bid/ask cache.
+    pub fn set_reference_price(&self, reference_price: u128) {
+        self.reference_price.store(Some(reference_price));
+        self.pegged
+            .write()
+            .unwrap()
+            .reprice_all(Some(reference_price));
+        self.cache.invalidate();
+        self.recompute_cache();
+    }
+
+    /// Places an oracle-pegged order. Its effective price is
+    /// `reference_price + peg_offset`, bounded by `limit_price`; if no
+    /// reference price has been set yet, or the computed price would
+    /// cross `limit_price`, the order is parked until
+    /// [`Self::set_reference_price`] brings it back in range.
+    pub fn add_oracle_pegged_order(
+        &self,
+        id: OrderId,
+        side: Side,
+        peg_offset: i128,
+        limit_price: Option<u128>,
+        quantity: u128,
+        time_in_force: TimeInForce,
+        user: Option<Hash32>,
+    ) -> Result<(), OrderBookError> {
+        if quantity == 0 {
+            return Err(OrderBookError::InvalidQuantity);
+        }
+        let order = OraclePeggedOrder {
+            id,
+            side,
+            peg_offset,
+            limit_price,
+            quantity,
+            time_in_force,
+            user,
+        };
+        self.pegged
+            .write()
+            .unwrap()
+            .insert(order, self.reference_price.load());
+        self.locations
+            .write()
+            .unwrap()
+            .insert(id, OrderLocation::Pegged { side });
+        self.emit(BookEvent::Placed { id, user });
+        self.recompute_cache();
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------
+    // Mass cancellation
+    // -----------------------------------------------------------------
+
+    /// Cancels every resting order on both sides, fixed and pegged.
+    pub fn cancel_all_orders(&self) -> MassCancelResult {
+        let mut result = MassCancelResult::default();
+        {
+            let mut bids = self.bids.write().unwrap();
+            let mut asks = self.asks.write().unwrap();
+            result.extend(bids.values().flatten().map(|o| o.id));
+            result.extend(asks.values().flatten().map(|o| o.id));
+            bids.clear();
+            asks.clear();
+        }
+        {
+            let mut pegged = self.pegged.write().unwrap();
+            result.extend(pegged.drain_all().into_iter().map(|o| o.id));
+        }
+        self.locations.write().unwrap().clear();
+        self.emit_mass_cancelled(&result);
+        self.recompute_cache();
+        result
+    }
+
+    /// Cancels every resting order (fixed or pegged) on `side`.
+    pub fn cancel_orders_by_side(&self, side: Side) -> MassCancelResult {
+        let mut result = MassCancelResult::default();
+        let levels = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+        {
+            let mut levels = levels.write().unwrap();
+            result.extend(levels.values().flatten().map(|o| o.id));
+            levels.clear();
+        }
+        {
+            let mut pegged = self.pegged.write().unwrap();
+            result.extend(pegged.drain_side(side).into_iter().map(|o| o.id));
+        }
+        let mut locations = self.locations.write().unwrap();
+        for id in result.cancelled_order_ids() {
+            locations.remove(id);
+        }
+        drop(locations);
+        self.emit_mass_cancelled(&result);
+        self.recompute_cache();
+        result
+    }
+
+    /// Cancels every resting order (fixed or pegged) placed by `user`.
+    pub fn cancel_orders_by_user(&self, user: Hash32) -> MassCancelResult {
+        let mut result = MassCancelResult::default();
+        {
+            let mut bids = self.bids.write().unwrap();
+            let mut asks = self.asks.write().unwrap();
+            for levels in [&mut *bids, &mut *asks] {
+                for queue in levels.values_mut() {
+                    let removed: Vec<_> = queue
+                        .iter()
+                        .filter(|o| o.user == Some(user))
+                        .map(|o| o.id)
+                        .collect();
+                    queue.retain(|o| o.user != Some(user));
+                    result.extend(removed);
+                }
+                levels.retain(|_, q| !q.is_empty());
+            }
+        }
+        {
+            let mut pegged = self.pegged.write().unwrap();
+            result.extend(
+                pegged
+                    .remove_matching(|o| o.user == Some(user))
+                    .into_iter()
+                    .map(|o| o.id),
+            );
+        }
+        let mut locations = self.locations.write().unwrap();
+        for id in result.cancelled_order_ids() {
+            locations.remove(id);
+        }
+        drop(locations);
+        self.emit_mass_cancelled(&result);
+        self.recompute_cache();
+        result
+    }
+
+    /// Cancels every resting order (fixed or pegged, by current
+    /// effective price) on `side` whose price falls within
+    /// `[low, high]` inclusive.
+    pub fn cancel_orders_by_price_range(&self, side: Side, low: u128, high: u128) -> MassCancelResult {
+        let mut result = MassCancelResult::default();
+        if low > high {
+            return result;
+        }
+        let levels = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+        {
+            let mut levels = levels.write().unwrap();
+            let prices_in_range: Vec<u128> = levels.range(low..=high).map(|(p, _)| *p).collect();
+            for price in prices_in_range {
+                if let Some(queue) = levels.remove(&price) {
+                    result.extend(queue.into_iter().map(|o| o.id));
+                }
+            }
+        }
+        {
+            let mut pegged = self.pegged.write().unwrap();
+            result.extend(
+                pegged
+                    .remove_matching(|o| {
+                        o.side == side
+                            && o.effective_price().is_some_and(|p| p >= low && p <= high)
+                    })
+                    .into_iter()
+                    .map(|o| o.id),
+            );
+        }
+        let mut locations = self.locations.write().unwrap();
+        for id in result.cancelled_order_ids() {
+            locations.remove(id);
+        }
+        drop(locations);
+        self.emit_mass_cancelled(&result);
+        self.recompute_cache();
+        result
+    }
+
+    // -----------------------------------------------------------------
+    // Bounded mass cancellation
+    // -----------------------------------------------------------------
+
+    /// Like [`Self::cancel_all_orders`], but cancels at most `max`
+    /// orders per call. [`MassCancelResult::more_remaining`] reports
+    /// whether any matching orders were left resting; callers that need
+    /// everything gone should call this in a loop until it returns
+    /// `false`.
+    pub fn cancel_all_orders_limited(&self, max: usize) -> MassCancelResult {
+        let candidates = self.locations.read().unwrap().keys().copied().collect();
+        self.cancel_capped(candidates, max)
+    }
+
+    /// Like [`Self::cancel_orders_by_side`], bounded to at most `max`
+    /// orders per call.
+    pub fn cancel_orders_by_side_limited(&self, side: Side, max: usize) -> MassCancelResult {
+        let candidates = self.ids_on_side(side);
+        self.cancel_capped(candidates, max)
+    }
+
+    /// Like [`Self::cancel_orders_by_user`], bounded to at most `max`
+    /// orders per call.
+    pub fn cancel_orders_by_user_limited(&self, user: Hash32, max: usize) -> MassCancelResult {
+        let candidates = self.ids_by_user(user);
+        self.cancel_capped(candidates, max)
+    }
+
+    fn ids_on_side(&self, side: Side) -> Vec<OrderId> {
+        let levels = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+        let mut ids: Vec<OrderId> = levels
+            .read()
+            .unwrap()
+            .values()
+            .flatten()
+            .map(|o| o.id)
+            .collect();
+        ids.extend(self.pegged.read().unwrap().ids(side));
+        ids
+    }
+
+    fn ids_by_user(&self, user: Hash32) -> Vec<OrderId> {
+        let mut ids: Vec<OrderId> = Vec::new();
+        for levels in [&self.bids, &self.asks] {
+            ids.extend(
+                levels
+                    .read()
+                    .unwrap()
+                    .values()
+                    .flatten()
+                    .filter(|o| o.user == Some(user))
+                    .map(|o| o.id),
+            );
+        }
+        ids.extend(
+            self.pegged
+                .read()
+                .unwrap()
+                .ids_matching(|o| o.user == Some(user)),
+        );
+        ids
+    }
+
+    /// Cancels at most `max` of `candidates`, reporting the rest as
+    /// skipped via [`MassCancelResult::skipped_count`].
+    fn cancel_capped(&self, candidates: Vec<OrderId>, max: usize) -> MassCancelResult {
+        let mut result = MassCancelResult::default();
+        let take = candidates.len().min(max);
+        for id in &candidates[..take] {
+            if self.remove_resting_order(*id) {
+                result.push(*id);
+            }
+        }
+        result.set_skipped(candidates.len() - take);
+        self.emit_mass_cancelled(&result);
+        self.recompute_cache();
+        result
+    }
+
+    /// Removes a single resting order (fixed or pegged) by id,
+    /// cleaning up its `locations` entry. Returns `false` if the order
+    /// was not found (e.g. already filled or cancelled).
+    fn remove_resting_order(&self, id: OrderId) -> bool {
+        let location = self.locations.write().unwrap().remove(&id);
+        match location {
+            Some(OrderLocation::Fixed { side, price }) => {
+                let levels = match side {
+                    Side::Buy => &self.bids,
+                    Side::Sell => &self.asks,
+                };
+                let mut levels = levels.write().unwrap();
+                let Some(queue) = levels.get_mut(&price) else {
+                    return false;
+                };
+                let Some(pos) = queue.iter().position(|o| o.id == id) else {
+                    return false;
+                };
+                queue.remove(pos);
+                if queue.is_empty() {
+                    levels.remove(&price);
+                }
+                true
+            }
+            Some(OrderLocation::Pegged { .. }) => self.pegged.write().unwrap().remove(id).is_some(),
+            None => false,
+        }
+    }
+
+    // -----------------------------------------------------------------
+    // GTD expiration
+    // -----------------------------------------------------------------
+
+    /// Sweeps both sides (fixed and pegged) for every `Gtd` order whose
+    /// expiry is at or before `now_ts`, removing it from the book and
+    /// cleaning its `locations` entry. Also advances the clock used by
+    /// the timestamp-free [`Self::best_bid`]/[`Self::best_ask`], so
+    /// they stop reporting a dead level as soon as it's swept even if
+    /// nothing else has queried it since.
+    ///
+    /// Order entry and matching take their own `now_ts` from the caller
+    /// (see [`Self::match_incoming`]) and so skip expired orders as of
+    /// that timestamp regardless of when this was last called; calling
+    /// this regularly is only needed to actually remove expired orders
+    /// from the book rather than merely skip them.
+    pub fn cancel_expired_orders(&self, now_ts: u64) -> MassCancelResult {
+        self.clock.store(now_ts);
+        let mut result = MassCancelResult::default();
+        {
+            let mut bids = self.bids.write().unwrap();
+            let mut asks = self.asks.write().unwrap();
+            for levels in [&mut *bids, &mut *asks] {
+                for queue in levels.values_mut() {
+                    let expired: Vec<_> = queue
+                        .iter()
+                        .filter(|o| Self::is_expired(o.time_in_force, now_ts))
+                        .map(|o| o.id)
+                        .collect();
+                    queue.retain(|o| !Self::is_expired(o.time_in_force, now_ts));
+                    result.extend(expired);
+                }
+                levels.retain(|_, q| !q.is_empty());
+            }
+        }
+        {
+            let mut pegged = self.pegged.write().unwrap();
+            result.extend(
+                pegged
+                    .remove_matching(|o| Self::is_expired(o.time_in_force, now_ts))
+                    .into_iter()
+                    .map(|o| o.id),
+            );
+        }
+        let mut locations = self.locations.write().unwrap();
+        for id in result.cancelled_order_ids() {
+            locations.remove(id);
+        }
+        drop(locations);
+        self.emit_mass_cancelled(&result);
+        self.recompute_cache();
+        result
+    }
+
+    // -----------------------------------------------------------------
+    // Market orders
+    // -----------------------------------------------------------------
+
+    /// Executes `quantity` as an immediate market order: sweeps the
+    /// opposite side by price-time priority and, unlike a crossing limit
+    /// order, never rests the remainder. Self-trade prevention still
+    /// applies, so `STPMode::CancelTaker`/`CancelBoth` can abort the
+    /// sweep early if it would cross a same-user maker. See
+    /// [`Self::add_limit_order`] for the meaning of `now_ts`.
+    ///
+    /// Unlike a resting limit order, this never emits `PartiallyFilled`
+    /// — a market order is never still resting, so any quantity traded
+    /// is reported via `Filled` and any shortfall via
+    /// `Cancelled(Unfilled)`, both on the same call if the order only
+    /// partially filled.
+    pub fn execute_market_order(
+        &self,
+        id: OrderId,
+        side: Side,
+        quantity: u128,
+        now_ts: u64,
+        user: Option<Hash32>,
+    ) -> Result<MarketOrderResult, OrderBookError> {
+        if quantity == 0 {
+            return Err(OrderBookError::InvalidQuantity);
+        }
+
+        let outcome = self.match_incoming(side, None, quantity, user, now_ts);
+        if outcome.filled > 0 {
+            self.emit(BookEvent::Filled { id, user });
+        }
+        if outcome.remaining > 0 {
+            self.emit(BookEvent::Cancelled {
+                id,
+                user,
+                reason: CancelReason::Unfilled,
+            });
+        }
+        self.recompute_cache();
+
+        Ok(MarketOrderResult {
+            filled_qty: outcome.filled,
+            avg_fill_price: (outcome.filled > 0).then(|| outcome.notional / outcome.filled),
+            unfilled_qty: outcome.remaining,
+        })
+    }
+}
@@ -0,0 +1,211 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 26/7/26
+******************************************************************************/
+
+//! Oracle-pegged orders: resting orders whose price tracks an external
+//! reference price rather than a fixed level, modeled on Mango's
+//! oracle-peg perp orders.
+//!
+//! A pegged order stores a signed `peg_offset` instead of an absolute
+//! price. Its effective price is `reference_price + peg_offset`, bounded
+//! by an optional `limit_price` that protects the resting side from an
+//! adverse reference move. When the computed price would cross its own
+//! `limit_price` the order is parked (treated as invalid) until the
+//! reference moves back in its favor.
+
+use crate::orderbook::order::is_expired;
+use pricelevel::{Hash32, OrderId, Side, TimeInForce};
+
+/// A single oracle-pegged resting order.
+#[derive(Debug, Clone)]
+pub(crate) struct OraclePeggedOrder {
+    pub id: OrderId,
+    pub side: Side,
+    pub peg_offset: i128,
+    pub limit_price: Option<u128>,
+    pub quantity: u128,
+    pub time_in_force: TimeInForce,
+    pub user: Option<Hash32>,
+    /// `reference_price + peg_offset`, or `None` while the order is
+    /// parked (including when that price would cross `limit_price`).
+    effective_price: Option<u128>,
+}
+
+impl OraclePeggedOrder {
+    /// Recomputes `effective_price` against a new reference price,
+    /// parking the order if no reference has been set yet (`None`) or
+    /// it would now cross its own `limit_price`.
+    fn reprice(&mut self, reference_price: Option<u128>) {
+        let Some(reference_price) = reference_price else {
+            self.effective_price = None;
+            return;
+        };
+        let raw = reference_price as i128 + self.peg_offset;
+        if raw <= 0 {
+            self.effective_price = None;
+            return;
+        }
+        let price = raw as u128;
+        let within_limit = match (self.side, self.limit_price) {
+            (Side::Buy, Some(limit)) => price <= limit,
+            (Side::Sell, Some(limit)) => price >= limit,
+            (_, None) => true,
+        };
+        self.effective_price = within_limit.then_some(price);
+    }
+
+    pub fn effective_price(&self) -> Option<u128> {
+        self.effective_price
+    }
+}
+
+/// Per-side storage for oracle-pegged orders, interleaved separately
+/// from the fixed price tree so a reference update only has to
+/// re-sort this (typically much smaller) set.
+#[derive(Debug)]
+pub(crate) struct OraclePeggedBook {
+    buys: Vec<OraclePeggedOrder>,
+    sells: Vec<OraclePeggedOrder>,
+}
+
+impl Default for OraclePeggedBook {
+    fn default() -> Self {
+        Self {
+            buys: Vec::new(),
+            sells: Vec::new(),
+        }
+    }
+}
+
+impl OraclePeggedBook {
+    fn side_mut(&mut self, side: Side) -> &mut Vec<OraclePeggedOrder> {
+        match side {
+            Side::Buy => &mut self.buys,
+            Side::Sell => &mut self.sells,
+        }
+    }
+
+    fn side(&self, side: Side) -> &Vec<OraclePeggedOrder> {
+        match side {
+            Side::Buy => &self.buys,
+            Side::Sell => &self.sells,
+        }
+    }
+
+    /// Inserts a new pegged order, pricing it against the current
+    /// reference immediately. `reference_price` is `None` until
+    /// `OrderBook::set_reference_price` has been called at least once,
+    /// in which case the order is parked rather than guessed at.
+    pub fn insert(&mut self, mut order: OraclePeggedOrder, reference_price: Option<u128>) {
+        order.reprice(reference_price);
+        self.side_mut(order.side).push(order);
+    }
+
+    /// Recomputes every pegged order's effective price against a new
+    /// reference. Called whenever `OrderBook::set_reference_price` runs.
+    pub fn reprice_all(&mut self, reference_price: Option<u128>) {
+        for order in self.buys.iter_mut().chain(self.sells.iter_mut()) {
+            order.reprice(reference_price);
+        }
+    }
+
+    /// The most aggressive valid, non-expired pegged order resting on
+    /// `side` as of `now_ts`, i.e. highest effective price for buys,
+    /// lowest for sells; used by the matching engine and the best-price
+    /// path so a taker (or a best-bid/ask read) never sees a pegged
+    /// maker that should already be dead.
+    pub fn best_valid(&self, side: Side, now_ts: u64) -> Option<&OraclePeggedOrder> {
+        self.side(side)
+            .iter()
+            .rev()
+            .filter(|o| o.effective_price.is_some() && !is_expired(o.time_in_force, now_ts))
+            .max_by_key(|o| match side {
+                Side::Buy => o.effective_price,
+                Side::Sell => o.effective_price.map(|p| u128::MAX - p),
+            })
+    }
+
+    /// Ids of every pegged order resting on `side`, in no particular
+    /// order; used by the bounded mass-cancel variants to build a
+    /// candidate set without removing anything yet.
+    pub fn ids(&self, side: Side) -> Vec<OrderId> {
+        self.side(side).iter().map(|o| o.id).collect()
+    }
+
+    /// Ids of every pegged order (either side) matching `predicate`.
+    pub fn ids_matching(&self, mut predicate: impl FnMut(&OraclePeggedOrder) -> bool) -> Vec<OrderId> {
+        self.buys
+            .iter()
+            .chain(self.sells.iter())
+            .filter(|o| predicate(o))
+            .map(|o| o.id)
+            .collect()
+    }
+
+    pub fn remove(&mut self, id: OrderId) -> Option<OraclePeggedOrder> {
+        for side in [Side::Buy, Side::Sell] {
+            let vec = self.side_mut(side);
+            if let Some(pos) = vec.iter().position(|o| o.id == id) {
+                return Some(vec.remove(pos));
+            }
+        }
+        None
+    }
+
+    pub fn remove_matching(
+        &mut self,
+        mut predicate: impl FnMut(&OraclePeggedOrder) -> bool,
+    ) -> Vec<OraclePeggedOrder> {
+        let mut removed = Vec::new();
+        for side in [Side::Buy, Side::Sell] {
+            let vec = self.side_mut(side);
+            let mut i = 0;
+            while i < vec.len() {
+                if predicate(&vec[i]) {
+                    removed.push(vec.remove(i));
+                } else {
+                    i += 1;
+                }
+            }
+        }
+        removed
+    }
+
+    pub fn drain_side(&mut self, side: Side) -> Vec<OraclePeggedOrder> {
+        std::mem::take(self.side_mut(side))
+    }
+
+    pub fn drain_all(&mut self) -> Vec<OraclePeggedOrder> {
+        let mut all = std::mem::take(&mut self.buys);
+        all.extend(std::mem::take(&mut self.sells));
+        all
+    }
+
+    /// Removes and returns the most aggressive valid, non-expired
+    /// pegged order on `side`, for the matching engine to fill against.
+    /// Callers that only partially fill it are expected to hand the
+    /// remainder back via [`Self::put_back`].
+    pub fn pop_best_valid(&mut self, side: Side, now_ts: u64) -> Option<OraclePeggedOrder> {
+        let vec = self.side_mut(side);
+        let best_idx = vec
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, o)| o.effective_price.is_some() && !is_expired(o.time_in_force, now_ts))
+            .max_by_key(|(_, o)| match side {
+                Side::Buy => o.effective_price,
+                Side::Sell => o.effective_price.map(|p| u128::MAX - p),
+            })
+            .map(|(idx, _)| idx)?;
+        Some(vec.remove(best_idx))
+    }
+
+    /// Re-inserts an order that was removed via [`Self::pop_best_valid`] and
+    /// only partially filled, without re-pricing it. It goes back to the
+    /// front of its side so it keeps its time priority.
+    pub fn put_back(&mut self, order: OraclePeggedOrder) {
+        self.side_mut(order.side).insert(0, order);
+    }
+}
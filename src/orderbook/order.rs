@@ -0,0 +1,29 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 26/7/26
+******************************************************************************/
+
+use pricelevel::{Hash32, OrderId, Side, TimeInForce};
+
+/// `true` if `time_in_force` is a `Gtd` order whose expiry is at or
+/// before `now_ts`. Shared by the fixed and oracle-pegged sides so
+/// "expired" means the same thing regardless of where an order rests.
+pub(crate) fn is_expired(time_in_force: TimeInForce, now_ts: u64) -> bool {
+    matches!(time_in_force, TimeInForce::Gtd(expiry_ts) if expiry_ts <= now_ts)
+}
+
+/// A resting order held in one of the book's fixed-price levels.
+///
+/// `T` is the caller-supplied metadata type threaded through
+/// [`OrderBook`](crate::OrderBook) unchanged.
+#[derive(Debug, Clone)]
+pub(crate) struct RestingOrder<T> {
+    pub id: OrderId,
+    pub price: u128,
+    pub quantity: u128,
+    pub side: Side,
+    pub time_in_force: TimeInForce,
+    pub user: Option<Hash32>,
+    pub metadata: Option<T>,
+}
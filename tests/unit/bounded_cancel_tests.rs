@@ -0,0 +1,202 @@
+//! Integration tests for the bounded (`_limited`) mass cancel variants.
+
+use orderbook_rs::OrderBook;
+use pricelevel::{Hash32, OrderId, Side, TimeInForce};
+
+fn new_book() -> OrderBook<()> {
+    OrderBook::new("TEST")
+}
+
+fn uid(byte: u8) -> Hash32 {
+    Hash32::new([byte; 32])
+}
+
+// ---------------------------------------------------------------------------
+// cancel_all_orders_limited
+// ---------------------------------------------------------------------------
+
+#[test]
+fn cancel_all_limited_under_cap_cancels_everything() {
+    let book = new_book();
+    for price in [90, 95, 100] {
+        book.add_limit_order(
+            OrderId::new_uuid(),
+            price,
+            10,
+            Side::Buy,
+            TimeInForce::Gtc,
+            1,
+            None,
+        )
+        .expect("add");
+    }
+
+    let result = book.cancel_all_orders_limited(10);
+    assert_eq!(result.cancelled_count(), 3);
+    assert_eq!(result.skipped_count(), 0);
+    assert!(!result.more_remaining());
+    assert_eq!(book.best_bid(), None);
+}
+
+#[test]
+fn cancel_all_limited_caps_and_reports_skipped() {
+    let book = new_book();
+    for price in [90, 95, 100] {
+        book.add_limit_order(
+            OrderId::new_uuid(),
+            price,
+            10,
+            Side::Buy,
+            TimeInForce::Gtc,
+            1,
+            None,
+        )
+        .expect("add");
+    }
+
+    let result = book.cancel_all_orders_limited(2);
+    assert_eq!(result.cancelled_count(), 2);
+    assert_eq!(result.skipped_count(), 1);
+    assert!(result.more_remaining());
+    assert_eq!(book.best_bid().is_some(), true);
+}
+
+#[test]
+fn cancel_all_limited_in_a_loop_drains_the_book() {
+    let book = new_book();
+    for price in [90, 95, 100, 105, 110] {
+        book.add_limit_order(
+            OrderId::new_uuid(),
+            price,
+            10,
+            Side::Buy,
+            TimeInForce::Gtc,
+            1,
+            None,
+        )
+        .expect("add");
+    }
+
+    let mut total_cancelled = 0;
+    loop {
+        let result = book.cancel_all_orders_limited(2);
+        total_cancelled += result.cancelled_count();
+        if !result.more_remaining() {
+            break;
+        }
+    }
+
+    assert_eq!(total_cancelled, 5);
+    assert_eq!(book.best_bid(), None);
+}
+
+// ---------------------------------------------------------------------------
+// cancel_orders_by_side_limited
+// ---------------------------------------------------------------------------
+
+#[test]
+fn cancel_by_side_limited_only_counts_the_requested_side() {
+    let book = new_book();
+    book.add_limit_order(OrderId::new_uuid(), 100, 10, Side::Buy, TimeInForce::Gtc, 1, None)
+        .expect("bid 1");
+    book.add_limit_order(OrderId::new_uuid(), 95, 10, Side::Buy, TimeInForce::Gtc, 1, None)
+        .expect("bid 2");
+    book.add_limit_order(OrderId::new_uuid(), 200, 10, Side::Sell, TimeInForce::Gtc, 1, None)
+        .expect("ask");
+
+    let result = book.cancel_orders_by_side_limited(Side::Buy, 1);
+    assert_eq!(result.cancelled_count(), 1);
+    assert_eq!(result.skipped_count(), 1);
+    assert!(result.more_remaining());
+    assert_eq!(book.best_ask(), Some(200));
+}
+
+// ---------------------------------------------------------------------------
+// cancel_orders_by_user_limited
+// ---------------------------------------------------------------------------
+
+#[test]
+fn cancel_by_user_limited_caps_and_reports_skipped() {
+    let book = new_book();
+    let user = uid(1);
+
+    for price in [100, 95, 90] {
+        book.add_limit_order_with_user(
+            OrderId::new_uuid(),
+            price,
+            10,
+            Side::Buy,
+            TimeInForce::Gtc,
+            1,
+            user,
+            None,
+        )
+        .expect("add");
+    }
+
+    let result = book.cancel_orders_by_user_limited(user, 2);
+    assert_eq!(result.cancelled_count(), 2);
+    assert_eq!(result.skipped_count(), 1);
+    assert!(result.more_remaining());
+}
+
+#[test]
+fn cancel_by_user_limited_does_not_touch_other_users() {
+    let book = new_book();
+    let user_a = uid(1);
+    let user_b = uid(2);
+
+    book.add_limit_order_with_user(
+        OrderId::new_uuid(),
+        100,
+        10,
+        Side::Buy,
+        TimeInForce::Gtc,
+        1,
+        user_a,
+        None,
+    )
+    .expect("a");
+    book.add_limit_order_with_user(
+        OrderId::new_uuid(),
+        95,
+        10,
+        Side::Buy,
+        TimeInForce::Gtc,
+        1,
+        user_b,
+        None,
+    )
+    .expect("b");
+
+    let result = book.cancel_orders_by_user_limited(user_a, 10);
+    assert_eq!(result.cancelled_count(), 1);
+    assert!(!result.more_remaining());
+    assert_eq!(book.best_bid(), Some(95));
+}
+
+// ---------------------------------------------------------------------------
+// MassCancelResult bookkeeping
+// ---------------------------------------------------------------------------
+
+#[test]
+fn limited_cancel_on_empty_book_reports_nothing() {
+    let book = new_book();
+    let result = book.cancel_all_orders_limited(10);
+    assert!(result.is_empty());
+    assert_eq!(result.skipped_count(), 0);
+    assert!(!result.more_remaining());
+}
+
+#[test]
+fn limited_cancel_of_zero_skips_everything() {
+    let book = new_book();
+    book.add_limit_order(OrderId::new_uuid(), 100, 10, Side::Buy, TimeInForce::Gtc, 1, None)
+        .expect("add");
+
+    let result = book.cancel_all_orders_limited(0);
+    assert_eq!(result.cancelled_count(), 0);
+    assert_eq!(result.skipped_count(), 1);
+    assert!(result.more_remaining());
+    assert_eq!(book.best_bid(), Some(100));
+}
@@ -0,0 +1,171 @@
+//! Integration tests for `MarketConstraints` (tick size / lot size / min size).
+
+use orderbook_rs::orderbook::{MarketConstraints, OrderBookError};
+use orderbook_rs::OrderBook;
+use pricelevel::{OrderId, Side, TimeInForce};
+
+fn constrained_book(constraints: MarketConstraints) -> OrderBook<()> {
+    OrderBook::new_with_constraints("TEST", constraints)
+}
+
+// ---------------------------------------------------------------------------
+// tick_size
+// ---------------------------------------------------------------------------
+
+#[test]
+fn rejects_price_not_a_multiple_of_tick_size() {
+    let book = constrained_book(MarketConstraints {
+        tick_size: 5,
+        lot_size: 1,
+        min_size: 1,
+    });
+
+    let err = book
+        .add_limit_order(OrderId::new_uuid(), 102, 10, Side::Buy, TimeInForce::Gtc, 1, None)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        OrderBookError::InvalidTickSize {
+            price: 102,
+            tick_size: 5
+        }
+    );
+}
+
+#[test]
+fn accepts_price_that_is_a_multiple_of_tick_size() {
+    let book = constrained_book(MarketConstraints {
+        tick_size: 5,
+        lot_size: 1,
+        min_size: 1,
+    });
+
+    book.add_limit_order(OrderId::new_uuid(), 100, 10, Side::Buy, TimeInForce::Gtc, 1, None)
+        .expect("multiple of tick size");
+    assert_eq!(book.best_bid(), Some(100));
+}
+
+// ---------------------------------------------------------------------------
+// lot_size
+// ---------------------------------------------------------------------------
+
+#[test]
+fn rejects_quantity_not_a_multiple_of_lot_size() {
+    let book = constrained_book(MarketConstraints {
+        tick_size: 1,
+        lot_size: 10,
+        min_size: 1,
+    });
+
+    let err = book
+        .add_limit_order(OrderId::new_uuid(), 100, 15, Side::Buy, TimeInForce::Gtc, 1, None)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        OrderBookError::InvalidLotSize {
+            quantity: 15,
+            lot_size: 10
+        }
+    );
+}
+
+// ---------------------------------------------------------------------------
+// min_size
+// ---------------------------------------------------------------------------
+
+#[test]
+fn rejects_quantity_below_minimum_size() {
+    let book = constrained_book(MarketConstraints {
+        tick_size: 1,
+        lot_size: 1,
+        min_size: 10,
+    });
+
+    let err = book
+        .add_limit_order(OrderId::new_uuid(), 100, 5, Side::Buy, TimeInForce::Gtc, 1, None)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        OrderBookError::OrderBelowMinimumSize {
+            quantity: 5,
+            min_size: 10
+        }
+    );
+}
+
+#[test]
+fn accepts_quantity_exactly_at_minimum_size() {
+    let book = constrained_book(MarketConstraints {
+        tick_size: 1,
+        lot_size: 1,
+        min_size: 10,
+    });
+
+    book.add_limit_order(OrderId::new_uuid(), 100, 10, Side::Buy, TimeInForce::Gtc, 1, None)
+        .expect("exactly at minimum");
+}
+
+// ---------------------------------------------------------------------------
+// Enforced across every constrained entry point
+// ---------------------------------------------------------------------------
+
+#[test]
+fn iceberg_order_is_validated_against_constraints() {
+    let book = constrained_book(MarketConstraints {
+        tick_size: 5,
+        lot_size: 1,
+        min_size: 1,
+    });
+
+    let err = book
+        .add_iceberg_order(
+            OrderId::new_uuid(),
+            101,
+            5,
+            15,
+            Side::Buy,
+            TimeInForce::Gtc,
+            1,
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        OrderBookError::InvalidTickSize {
+            price: 101,
+            tick_size: 5
+        }
+    );
+}
+
+#[test]
+fn post_only_order_is_validated_against_constraints() {
+    let book = constrained_book(MarketConstraints {
+        tick_size: 1,
+        lot_size: 1,
+        min_size: 10,
+    });
+
+    let err = book
+        .add_post_only_order(OrderId::new_uuid(), 100, 5, Side::Buy, TimeInForce::Gtc, 1, None)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        OrderBookError::OrderBelowMinimumSize {
+            quantity: 5,
+            min_size: 10
+        }
+    );
+}
+
+// ---------------------------------------------------------------------------
+// No constraints configured: behaves exactly as an unconstrained book
+// ---------------------------------------------------------------------------
+
+#[test]
+fn unconstrained_book_accepts_any_price_and_quantity() {
+    let book: OrderBook<()> = OrderBook::new("TEST");
+    book.add_limit_order(OrderId::new_uuid(), 3, 7, Side::Buy, TimeInForce::Gtc, 1, None)
+        .expect("no constraints configured");
+    assert_eq!(book.best_bid(), Some(3));
+}
@@ -0,0 +1,178 @@
+//! Integration tests for the `BookEvent` subscription stream.
+
+use orderbook_rs::orderbook::{BookEvent, CancelReason};
+use orderbook_rs::{OrderBook, STPMode};
+use pricelevel::{Hash32, OrderId, Side, TimeInForce};
+
+fn new_book() -> OrderBook<()> {
+    OrderBook::new("TEST")
+}
+
+fn uid(byte: u8) -> Hash32 {
+    Hash32::new([byte; 32])
+}
+
+#[test]
+fn placed_event_emitted_when_order_rests() {
+    let book = new_book();
+    let rx = book.subscribe();
+    let id = OrderId::new_uuid();
+
+    book.add_limit_order(id, 100, 10, Side::Buy, TimeInForce::Gtc, 1, None)
+        .expect("add");
+
+    match rx.try_recv().expect("an event") {
+        BookEvent::Placed { id: got, user } => {
+            assert_eq!(got, id);
+            assert_eq!(user, None);
+        }
+        other => panic!("expected Placed, got {other:?}"),
+    }
+    assert!(rx.try_recv().is_err());
+}
+
+#[test]
+fn filled_event_emitted_on_both_sides_of_a_full_match() {
+    let book = new_book();
+    let rx = book.subscribe();
+    let maker_id = OrderId::new_uuid();
+    let taker_id = OrderId::new_uuid();
+
+    book.add_limit_order(maker_id, 100, 10, Side::Sell, TimeInForce::Gtc, 1, None)
+        .expect("maker rests");
+    // Drain the maker's Placed event before the taker arrives.
+    let _ = rx.try_recv();
+
+    book.add_limit_order(taker_id, 100, 10, Side::Buy, TimeInForce::Gtc, 2, None)
+        .expect("taker fully fills");
+
+    let mut events = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        events.push(event);
+    }
+    assert_eq!(events.len(), 2);
+    assert!(events
+        .iter()
+        .any(|e| matches!(e, BookEvent::Filled { id, .. } if *id == maker_id)));
+    assert!(events
+        .iter()
+        .any(|e| matches!(e, BookEvent::Filled { id, .. } if *id == taker_id)));
+}
+
+#[test]
+fn partially_filled_event_reports_remaining_quantity() {
+    let book = new_book();
+    let maker_id = OrderId::new_uuid();
+    book.add_limit_order(maker_id, 100, 20, Side::Sell, TimeInForce::Gtc, 1, None)
+        .expect("maker rests");
+
+    let rx = book.subscribe();
+    book.add_limit_order(OrderId::new_uuid(), 100, 5, Side::Buy, TimeInForce::Gtc, 2, None)
+        .expect("taker partially fills the maker and fully fills itself");
+
+    let mut saw_maker_partial = false;
+    let mut saw_taker_filled = false;
+    while let Ok(event) = rx.try_recv() {
+        match event {
+            BookEvent::PartiallyFilled {
+                id,
+                filled_qty,
+                remaining,
+                ..
+            } if id == maker_id => {
+                assert_eq!(filled_qty, 5);
+                assert_eq!(remaining, 15);
+                saw_maker_partial = true;
+            }
+            BookEvent::Filled { .. } => saw_taker_filled = true,
+            _ => {}
+        }
+    }
+    assert!(saw_maker_partial);
+    assert!(saw_taker_filled);
+}
+
+#[test]
+fn cancelled_self_trade_event_emitted_when_stp_cancels_maker() {
+    let mut book: OrderBook<()> = OrderBook::new("TEST");
+    book.set_stp_mode(STPMode::CancelMaker);
+    let user = uid(1);
+
+    let maker_id = OrderId::new_uuid();
+    book.add_limit_order_with_user(maker_id, 100, 10, Side::Sell, TimeInForce::Gtc, 1, user, None)
+        .expect("maker rests");
+
+    let rx = book.subscribe();
+    book.add_limit_order_with_user(
+        OrderId::new_uuid(),
+        100,
+        10,
+        Side::Buy,
+        TimeInForce::Gtc,
+        2,
+        user,
+        None,
+    )
+    .expect("same-user taker triggers STP");
+
+    let mut saw_self_trade_cancel = false;
+    while let Ok(event) = rx.try_recv() {
+        if let BookEvent::Cancelled { id, reason, .. } = event {
+            assert_eq!(id, maker_id);
+            assert_eq!(reason, CancelReason::SelfTrade);
+            saw_self_trade_cancel = true;
+        }
+    }
+    assert!(saw_self_trade_cancel);
+}
+
+#[test]
+fn mass_cancelled_event_matches_mass_cancel_result() {
+    let book = new_book();
+    let id1 = OrderId::new_uuid();
+    let id2 = OrderId::new_uuid();
+    book.add_limit_order(id1, 100, 10, Side::Buy, TimeInForce::Gtc, 1, None)
+        .expect("add 1");
+    book.add_limit_order(id2, 95, 5, Side::Buy, TimeInForce::Gtc, 1, None)
+        .expect("add 2");
+
+    let rx = book.subscribe();
+    let result = book.cancel_all_orders();
+
+    let mut mass_cancelled_ids = None;
+    while let Ok(event) = rx.try_recv() {
+        if let BookEvent::MassCancelled { ids } = event {
+            mass_cancelled_ids = Some(ids);
+        }
+    }
+    let ids = mass_cancelled_ids.expect("a MassCancelled event");
+    assert_eq!(ids.len(), result.cancelled_count());
+    for id in result.cancelled_order_ids() {
+        assert!(ids.contains(id));
+    }
+}
+
+#[test]
+fn empty_mass_cancel_emits_no_event() {
+    let book = new_book();
+    let rx = book.subscribe();
+
+    let result = book.cancel_all_orders();
+    assert!(result.is_empty());
+    assert!(rx.try_recv().is_err());
+}
+
+#[test]
+fn dropped_subscriber_is_pruned_without_breaking_future_emits() {
+    let book = new_book();
+    {
+        let _rx = book.subscribe();
+        // Dropped at the end of this scope.
+    }
+
+    let rx2 = book.subscribe();
+    book.add_limit_order(OrderId::new_uuid(), 100, 10, Side::Buy, TimeInForce::Gtc, 1, None)
+        .expect("emitting after a dead subscriber was dropped should not panic");
+
+    assert!(rx2.try_recv().is_ok());
+}
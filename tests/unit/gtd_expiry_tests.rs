@@ -0,0 +1,222 @@
+//! Integration tests for `Gtd` expiration: `cancel_expired_orders`, and
+//! matching/best-price skipping expired resting orders ahead of a sweep.
+
+use orderbook_rs::OrderBook;
+use pricelevel::{OrderId, Side, TimeInForce};
+
+fn new_book() -> OrderBook<()> {
+    OrderBook::new("TEST")
+}
+
+// ---------------------------------------------------------------------------
+// cancel_expired_orders
+// ---------------------------------------------------------------------------
+
+#[test]
+fn cancel_expired_orders_removes_only_expired() {
+    let book = new_book();
+    let expired_id = OrderId::new_uuid();
+    let live_id = OrderId::new_uuid();
+
+    book.add_limit_order(
+        expired_id,
+        100,
+        10,
+        Side::Buy,
+        TimeInForce::Gtd(50),
+        1,
+        None,
+    )
+    .expect("expiring order");
+    book.add_limit_order(live_id, 95, 10, Side::Buy, TimeInForce::Gtd(200), 1, None)
+        .expect("live order");
+
+    let result = book.cancel_expired_orders(100);
+    assert_eq!(result.cancelled_count(), 1);
+    assert!(result.cancelled_order_ids().contains(&expired_id));
+    assert_eq!(book.best_bid(), Some(95));
+}
+
+#[test]
+fn cancel_expired_orders_ignores_gtc_orders() {
+    let book = new_book();
+    book.add_limit_order(OrderId::new_uuid(), 100, 10, Side::Buy, TimeInForce::Gtc, 1, None)
+        .expect("gtc order never expires");
+
+    let result = book.cancel_expired_orders(u64::MAX);
+    assert!(result.is_empty());
+    assert_eq!(book.best_bid(), Some(100));
+}
+
+#[test]
+fn cancel_expired_orders_sweeps_pegged_orders_too() {
+    let book = new_book();
+    book.set_reference_price(100);
+    let id = OrderId::new_uuid();
+    book.add_oracle_pegged_order(id, Side::Buy, 0, None, 10, TimeInForce::Gtd(50), None)
+        .expect("expiring pegged order");
+
+    let result = book.cancel_expired_orders(100);
+    assert_eq!(result.cancelled_count(), 1);
+    assert!(result.cancelled_order_ids().contains(&id));
+    assert_eq!(book.best_bid(), None);
+}
+
+#[test]
+fn expiry_at_exactly_now_ts_counts_as_expired() {
+    let book = new_book();
+    book.add_limit_order(
+        OrderId::new_uuid(),
+        100,
+        10,
+        Side::Buy,
+        TimeInForce::Gtd(100),
+        1,
+        None,
+    )
+    .expect("add");
+
+    let result = book.cancel_expired_orders(100);
+    assert_eq!(result.cancelled_count(), 1);
+}
+
+// ---------------------------------------------------------------------------
+// Matching skips (but does not remove) expired resting orders
+// ---------------------------------------------------------------------------
+
+#[test]
+fn taker_does_not_cross_an_expired_resting_order() {
+    let book = new_book();
+    let maker_id = OrderId::new_uuid();
+    book.add_limit_order(
+        maker_id,
+        100,
+        10,
+        Side::Sell,
+        TimeInForce::Gtd(50),
+        1,
+        None,
+    )
+    .expect("ask that will expire");
+
+    let taker_id = OrderId::new_uuid();
+    book.add_limit_order(taker_id, 100, 10, Side::Buy, TimeInForce::Gtc, 100, None)
+        .expect("taker arrives after the maker's expiry, no sweep yet");
+
+    // Matching skipped the expired maker: the taker rests instead of
+    // filling, and the maker is still sitting on the book unswept.
+    assert_eq!(book.best_bid(), Some(100));
+    assert_eq!(book.best_ask(), Some(100));
+}
+
+#[test]
+fn taker_crosses_a_live_gtd_order_before_expiry() {
+    let book = new_book();
+    book.add_limit_order(
+        OrderId::new_uuid(),
+        100,
+        10,
+        Side::Sell,
+        TimeInForce::Gtd(50),
+        1,
+        None,
+    )
+    .expect("ask expiring at 50");
+
+    book.add_limit_order(OrderId::new_uuid(), 100, 10, Side::Buy, TimeInForce::Gtc, 10, None)
+        .expect("taker arrives well before expiry");
+
+    assert_eq!(book.best_bid(), None);
+    assert_eq!(book.best_ask(), None);
+}
+
+#[test]
+fn market_order_does_not_fill_against_an_expired_resting_order() {
+    let book = new_book();
+    book.add_limit_order(
+        OrderId::new_uuid(),
+        100,
+        10,
+        Side::Sell,
+        TimeInForce::Gtd(50),
+        1,
+        None,
+    )
+    .expect("ask that will expire");
+
+    let result = book
+        .execute_market_order(OrderId::new_uuid(), Side::Buy, 10, 100, None)
+        .expect("market buy after expiry, no sweep yet");
+
+    assert_eq!(result.filled_qty, 0);
+    assert_eq!(result.unfilled_qty, 10);
+}
+
+// ---------------------------------------------------------------------------
+// best_bid/best_ask honor the expiry filter as of the last sweep;
+// add_post_only_order honors it as of its own now_ts
+// ---------------------------------------------------------------------------
+
+#[test]
+fn best_ask_reports_an_expired_level_until_it_is_swept() {
+    let book = new_book();
+    book.add_limit_order(
+        OrderId::new_uuid(),
+        100,
+        10,
+        Side::Sell,
+        TimeInForce::Gtd(50),
+        1,
+        None,
+    )
+    .expect("ask that will expire");
+    book.add_limit_order(
+        OrderId::new_uuid(),
+        110,
+        10,
+        Side::Sell,
+        TimeInForce::Gtc,
+        1,
+        None,
+    )
+    .expect("live ask behind it");
+
+    // No sweep has happened, but `best_ask` is read well after the
+    // expiry: the book's last-known clock is still 0 (nothing has
+    // advanced it), so this documents the "as of the last sweep"
+    // guarantee rather than a fully live one.
+    assert_eq!(book.best_ask(), Some(100));
+
+    book.cancel_expired_orders(100);
+    assert_eq!(book.best_ask(), Some(110));
+}
+
+#[test]
+fn post_only_order_is_not_rejected_by_a_dead_level() {
+    let book = new_book();
+    book.add_limit_order(
+        OrderId::new_uuid(),
+        100,
+        10,
+        Side::Sell,
+        TimeInForce::Gtd(50),
+        1,
+        None,
+    )
+    .expect("ask that will expire, never swept");
+
+    // As of now_ts = 100 the resting ask is already dead; a post-only
+    // buy at the same price must not be rejected as "would cross".
+    book.add_post_only_order(
+        OrderId::new_uuid(),
+        100,
+        5,
+        Side::Buy,
+        TimeInForce::Gtc,
+        100,
+        None,
+    )
+    .expect("post-only should not see the expired ask as crossing");
+
+    assert_eq!(book.best_bid(), Some(100));
+}
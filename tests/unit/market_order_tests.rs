@@ -0,0 +1,154 @@
+//! Integration tests for `execute_market_order`.
+
+use orderbook_rs::orderbook::{BookEvent, CancelReason, OrderBookError};
+use orderbook_rs::{OrderBook, STPMode};
+use pricelevel::{Hash32, OrderId, Side, TimeInForce};
+
+fn new_book() -> OrderBook<()> {
+    OrderBook::new("TEST")
+}
+
+fn uid(byte: u8) -> Hash32 {
+    Hash32::new([byte; 32])
+}
+
+#[test]
+fn market_order_rejects_zero_quantity() {
+    let book = new_book();
+    let err = book
+        .execute_market_order(OrderId::new_uuid(), Side::Buy, 0, 1, None)
+        .unwrap_err();
+    assert_eq!(err, OrderBookError::InvalidQuantity);
+}
+
+#[test]
+fn market_order_fully_fills_against_a_single_maker() {
+    let book = new_book();
+    book.add_limit_order(OrderId::new_uuid(), 100, 10, Side::Sell, TimeInForce::Gtc, 1, None)
+        .expect("maker ask");
+
+    let result = book
+        .execute_market_order(OrderId::new_uuid(), Side::Buy, 10, 2, None)
+        .expect("market buy");
+
+    assert_eq!(result.filled_qty, 10);
+    assert_eq!(result.avg_fill_price, Some(100));
+    assert_eq!(result.unfilled_qty, 0);
+    assert_eq!(book.best_ask(), None);
+}
+
+#[test]
+fn market_order_computes_volume_weighted_average_price_across_levels() {
+    let book = new_book();
+    book.add_limit_order(OrderId::new_uuid(), 100, 5, Side::Sell, TimeInForce::Gtc, 1, None)
+        .expect("ask at 100");
+    book.add_limit_order(OrderId::new_uuid(), 110, 5, Side::Sell, TimeInForce::Gtc, 1, None)
+        .expect("ask at 110");
+
+    let result = book
+        .execute_market_order(OrderId::new_uuid(), Side::Buy, 10, 2, None)
+        .expect("market buy across two levels");
+
+    // (100*5 + 110*5) / 10 = 105
+    assert_eq!(result.filled_qty, 10);
+    assert_eq!(result.avg_fill_price, Some(105));
+    assert_eq!(result.unfilled_qty, 0);
+}
+
+#[test]
+fn market_order_reports_unfilled_remainder_when_liquidity_runs_out() {
+    let book = new_book();
+    book.add_limit_order(OrderId::new_uuid(), 100, 4, Side::Sell, TimeInForce::Gtc, 1, None)
+        .expect("only 4 available");
+
+    let result = book
+        .execute_market_order(OrderId::new_uuid(), Side::Buy, 10, 2, None)
+        .expect("market buy for more than is resting");
+
+    assert_eq!(result.filled_qty, 4);
+    assert_eq!(result.avg_fill_price, Some(100));
+    assert_eq!(result.unfilled_qty, 6);
+}
+
+#[test]
+fn market_order_against_empty_book_fills_nothing() {
+    let book = new_book();
+    let result = book
+        .execute_market_order(OrderId::new_uuid(), Side::Buy, 10, 1, None)
+        .expect("market buy, nothing resting");
+
+    assert_eq!(result.filled_qty, 0);
+    assert_eq!(result.avg_fill_price, None);
+    assert_eq!(result.unfilled_qty, 10);
+}
+
+#[test]
+fn market_order_never_rests_its_unfilled_remainder() {
+    let book = new_book();
+    book.add_limit_order(OrderId::new_uuid(), 100, 3, Side::Sell, TimeInForce::Gtc, 1, None)
+        .expect("only 3 available");
+
+    book.execute_market_order(OrderId::new_uuid(), Side::Buy, 10, 2, None)
+        .expect("market buy for more than is resting");
+
+    // The unfilled 7 never posts to the book: no bid appears.
+    assert_eq!(book.best_bid(), None);
+    assert_eq!(book.best_ask(), None);
+}
+
+#[test]
+fn market_order_stp_cancel_taker_aborts_sweep_on_self_trade() {
+    let mut book: OrderBook<()> = OrderBook::new("TEST");
+    book.set_stp_mode(STPMode::CancelTaker);
+    let user = uid(1);
+
+    book.add_limit_order_with_user(
+        OrderId::new_uuid(),
+        100,
+        5,
+        Side::Sell,
+        TimeInForce::Gtc,
+        1,
+        user,
+        None,
+    )
+    .expect("same-user maker");
+    book.add_limit_order(OrderId::new_uuid(), 110, 5, Side::Sell, TimeInForce::Gtc, 1, None)
+        .expect("other-user maker behind it");
+
+    let result = book
+        .execute_market_order(OrderId::new_uuid(), Side::Buy, 10, 2, Some(user))
+        .expect("market buy aborts at the self-trade");
+
+    // The sweep stopped at the same-user maker without cancelling it.
+    assert_eq!(result.filled_qty, 0);
+    assert_eq!(result.unfilled_qty, 10);
+    assert_eq!(book.best_ask(), Some(100));
+}
+
+#[test]
+fn market_order_never_emits_partially_filled() {
+    let book = new_book();
+    book.add_limit_order(OrderId::new_uuid(), 100, 4, Side::Sell, TimeInForce::Gtc, 1, None)
+        .expect("only 4 available");
+
+    let rx = book.subscribe();
+    book.execute_market_order(OrderId::new_uuid(), Side::Buy, 10, 2, None)
+        .expect("partial market fill");
+
+    let mut saw_filled = false;
+    let mut saw_cancelled_unfilled = false;
+    while let Ok(event) = rx.try_recv() {
+        match event {
+            BookEvent::PartiallyFilled { .. } => panic!("market orders never rest"),
+            BookEvent::Filled { .. } => saw_filled = true,
+            BookEvent::Cancelled {
+                reason: CancelReason::Unfilled,
+                ..
+            } => saw_cancelled_unfilled = true,
+            _ => {}
+        }
+    }
+    assert!(saw_filled);
+    assert!(saw_cancelled_unfilled);
+}
@@ -40,6 +40,7 @@ fn cancel_all_removes_every_order() {
             10,
             Side::Buy,
             TimeInForce::Gtc,
+            1,
             None,
         )
         .expect("add bid");
@@ -51,6 +52,7 @@ fn cancel_all_removes_every_order() {
             10,
             Side::Sell,
             TimeInForce::Gtc,
+            1,
             None,
         )
         .expect("add ask");
@@ -68,7 +70,7 @@ fn cancel_all_removes_every_order() {
 fn cancel_all_cleans_order_locations() {
     let book = new_book();
     let id = OrderId::new_uuid();
-    book.add_limit_order(id, 100, 10, Side::Buy, TimeInForce::Gtc, None)
+    book.add_limit_order(id, 100, 10, Side::Buy, TimeInForce::Gtc, 1, None)
         .expect("add");
 
     let _ = book.cancel_all_orders();
@@ -89,6 +91,7 @@ fn cancel_by_side_buy_leaves_asks() {
         10,
         Side::Buy,
         TimeInForce::Gtc,
+        1,
         None,
     )
     .expect("bid");
@@ -98,6 +101,7 @@ fn cancel_by_side_buy_leaves_asks() {
         5,
         Side::Buy,
         TimeInForce::Gtc,
+        1,
         None,
     )
     .expect("bid 2");
@@ -107,6 +111,7 @@ fn cancel_by_side_buy_leaves_asks() {
         8,
         Side::Sell,
         TimeInForce::Gtc,
+        1,
         None,
     )
     .expect("ask");
@@ -128,6 +133,7 @@ fn cancel_by_side_sell_leaves_bids() {
         10,
         Side::Buy,
         TimeInForce::Gtc,
+        1,
         None,
     )
     .expect("bid");
@@ -137,6 +143,7 @@ fn cancel_by_side_sell_leaves_bids() {
         8,
         Side::Sell,
         TimeInForce::Gtc,
+        1,
         None,
     )
     .expect("ask");
@@ -146,6 +153,7 @@ fn cancel_by_side_sell_leaves_bids() {
         3,
         Side::Sell,
         TimeInForce::Gtc,
+        1,
         None,
     )
     .expect("ask 2");
@@ -166,6 +174,7 @@ fn cancel_by_side_on_empty_side() {
         10,
         Side::Buy,
         TimeInForce::Gtc,
+        1,
         None,
     )
     .expect("bid");
@@ -189,11 +198,11 @@ fn cancel_by_user_removes_only_matching_orders() {
     let id_a2 = OrderId::new_uuid();
     let id_b1 = OrderId::new_uuid();
 
-    book.add_limit_order_with_user(id_a1, 100, 10, Side::Buy, TimeInForce::Gtc, user_a, None)
+    book.add_limit_order_with_user(id_a1, 100, 10, Side::Buy, TimeInForce::Gtc, 1, user_a, None)
         .expect("a1");
-    book.add_limit_order_with_user(id_a2, 200, 5, Side::Sell, TimeInForce::Gtc, user_a, None)
+    book.add_limit_order_with_user(id_a2, 200, 5, Side::Sell, TimeInForce::Gtc, 1, user_a, None)
         .expect("a2");
-    book.add_limit_order_with_user(id_b1, 95, 20, Side::Buy, TimeInForce::Gtc, user_b, None)
+    book.add_limit_order_with_user(id_b1, 95, 20, Side::Buy, TimeInForce::Gtc, 1, user_b, None)
         .expect("b1");
 
     let result = book.cancel_orders_by_user(user_a);
@@ -218,6 +227,7 @@ fn cancel_by_user_no_match_returns_zero() {
         10,
         Side::Buy,
         TimeInForce::Gtc,
+        1,
         user_a,
         None,
     )
@@ -240,6 +250,7 @@ fn cancel_by_user_across_multiple_levels_and_sides() {
         10,
         Side::Buy,
         TimeInForce::Gtc,
+        1,
         user,
         None,
     )
@@ -250,6 +261,7 @@ fn cancel_by_user_across_multiple_levels_and_sides() {
         5,
         Side::Buy,
         TimeInForce::Gtc,
+        1,
         user,
         None,
     )
@@ -260,6 +272,7 @@ fn cancel_by_user_across_multiple_levels_and_sides() {
         8,
         Side::Sell,
         TimeInForce::Gtc,
+        1,
         user,
         None,
     )
@@ -270,6 +283,7 @@ fn cancel_by_user_across_multiple_levels_and_sides() {
         20,
         Side::Buy,
         TimeInForce::Gtc,
+        1,
         other,
         None,
     )
@@ -292,11 +306,11 @@ fn cancel_by_price_range_inclusive_boundaries() {
     let id2 = OrderId::new_uuid();
     let id3 = OrderId::new_uuid();
 
-    book.add_limit_order(id1, 100, 10, Side::Buy, TimeInForce::Gtc, None)
+    book.add_limit_order(id1, 100, 10, Side::Buy, TimeInForce::Gtc, 1, None)
         .expect("100");
-    book.add_limit_order(id2, 200, 10, Side::Buy, TimeInForce::Gtc, None)
+    book.add_limit_order(id2, 200, 10, Side::Buy, TimeInForce::Gtc, 1, None)
         .expect("200");
-    book.add_limit_order(id3, 300, 10, Side::Buy, TimeInForce::Gtc, None)
+    book.add_limit_order(id3, 300, 10, Side::Buy, TimeInForce::Gtc, 1, None)
         .expect("300");
 
     let result = book.cancel_orders_by_price_range(Side::Buy, 100, 200);
@@ -311,7 +325,7 @@ fn cancel_by_price_range_single_price() {
     let book = new_book();
 
     let id = OrderId::new_uuid();
-    book.add_limit_order(id, 150, 10, Side::Sell, TimeInForce::Gtc, None)
+    book.add_limit_order(id, 150, 10, Side::Sell, TimeInForce::Gtc, 1, None)
         .expect("add");
     book.add_limit_order(
         OrderId::new_uuid(),
@@ -319,6 +333,7 @@ fn cancel_by_price_range_single_price() {
         10,
         Side::Sell,
         TimeInForce::Gtc,
+        1,
         None,
     )
     .expect("add 2");
@@ -338,6 +353,7 @@ fn cancel_by_price_range_inverted_returns_zero() {
         10,
         Side::Buy,
         TimeInForce::Gtc,
+        1,
         None,
     )
     .expect("add");
@@ -356,6 +372,7 @@ fn cancel_by_price_range_no_orders_in_range() {
         10,
         Side::Buy,
         TimeInForce::Gtc,
+        1,
         None,
     )
     .expect("add");
@@ -371,9 +388,9 @@ fn cancel_by_price_range_multiple_orders_at_same_level() {
     let id1 = OrderId::new_uuid();
     let id2 = OrderId::new_uuid();
 
-    book.add_limit_order(id1, 100, 10, Side::Buy, TimeInForce::Gtc, None)
+    book.add_limit_order(id1, 100, 10, Side::Buy, TimeInForce::Gtc, 1, None)
         .expect("add 1");
-    book.add_limit_order(id2, 100, 20, Side::Buy, TimeInForce::Gtc, None)
+    book.add_limit_order(id2, 100, 20, Side::Buy, TimeInForce::Gtc, 1, None)
         .expect("add 2");
 
     let result = book.cancel_orders_by_price_range(Side::Buy, 100, 100);
@@ -391,6 +408,7 @@ fn cancel_by_price_range_on_wrong_side_returns_zero() {
         10,
         Side::Buy,
         TimeInForce::Gtc,
+        1,
         None,
     )
     .expect("add bid");
@@ -416,6 +434,7 @@ fn cancel_all_with_iceberg_orders() {
         15,
         Side::Buy,
         TimeInForce::Gtc,
+        1,
         None,
     )
     .expect("iceberg");
@@ -425,6 +444,7 @@ fn cancel_all_with_iceberg_orders() {
         10,
         Side::Sell,
         TimeInForce::Gtc,
+        1,
         None,
     )
     .expect("limit");
@@ -445,6 +465,7 @@ fn cancel_all_with_post_only_orders() {
         10,
         Side::Buy,
         TimeInForce::Gtc,
+        1,
         None,
     )
     .expect("post-only");
@@ -454,6 +475,7 @@ fn cancel_all_with_post_only_orders() {
         10,
         Side::Sell,
         TimeInForce::Gtc,
+        1,
         None,
     )
     .expect("limit");
@@ -501,6 +523,7 @@ fn cancel_by_user_on_stp_enabled_book() {
         10,
         Side::Buy,
         TimeInForce::Gtc,
+        1,
         user_a,
         None,
     )
@@ -511,6 +534,7 @@ fn cancel_by_user_on_stp_enabled_book() {
         5,
         Side::Sell,
         TimeInForce::Gtc,
+        1,
         user_b,
         None,
     )
@@ -535,6 +559,7 @@ fn double_cancel_all_is_idempotent() {
         10,
         Side::Buy,
         TimeInForce::Gtc,
+        1,
         None,
     )
     .expect("add");
@@ -556,6 +581,7 @@ fn cancel_by_side_then_cancel_all() {
         10,
         Side::Buy,
         TimeInForce::Gtc,
+        1,
         None,
     )
     .expect("bid");
@@ -565,6 +591,7 @@ fn cancel_by_side_then_cancel_all() {
         5,
         Side::Sell,
         TimeInForce::Gtc,
+        1,
         None,
     )
     .expect("ask");
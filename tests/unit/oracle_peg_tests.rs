@@ -0,0 +1,221 @@
+//! Integration tests for oracle-pegged orders.
+
+use orderbook_rs::orderbook::mass_cancel::MassCancelResult;
+use orderbook_rs::OrderBook;
+use pricelevel::{OrderId, Side, TimeInForce};
+
+fn new_book() -> OrderBook<()> {
+    OrderBook::new("TEST")
+}
+
+// ---------------------------------------------------------------------------
+// Parking: no reference price set yet
+// ---------------------------------------------------------------------------
+
+#[test]
+fn pegged_order_parks_until_reference_price_set() {
+    let book = new_book();
+
+    book.add_oracle_pegged_order(
+        OrderId::new_uuid(),
+        Side::Buy,
+        -5,
+        None,
+        10,
+        TimeInForce::Gtc,
+        None,
+    )
+    .expect("pegged buy");
+
+    // No reference price yet: the order is parked and invisible.
+    assert_eq!(book.best_bid(), None);
+
+    book.set_reference_price(100);
+    assert_eq!(book.best_bid(), Some(95));
+}
+
+#[test]
+fn pegged_order_parks_when_it_would_cross_its_own_limit_price() {
+    let book = new_book();
+
+    book.set_reference_price(100);
+    book.add_oracle_pegged_order(
+        OrderId::new_uuid(),
+        Side::Buy,
+        0,
+        Some(100),
+        10,
+        TimeInForce::Gtc,
+        None,
+    )
+    .expect("pegged buy at the edge of its limit");
+    assert_eq!(book.best_bid(), Some(100));
+
+    // Reference moves up: effective price would be 150, crossing the
+    // order's own limit of 100, so it parks instead of following.
+    book.set_reference_price(150);
+    assert_eq!(book.best_bid(), None);
+
+    // Reference moves back in range: the order reappears.
+    book.set_reference_price(100);
+    assert_eq!(book.best_bid(), Some(100));
+}
+
+// ---------------------------------------------------------------------------
+// Matching against pegged makers
+// ---------------------------------------------------------------------------
+
+#[test]
+fn incoming_order_fully_fills_pegged_maker() {
+    let book = new_book();
+    book.set_reference_price(100);
+
+    book.add_oracle_pegged_order(
+        OrderId::new_uuid(),
+        Side::Sell,
+        0,
+        None,
+        10,
+        TimeInForce::Gtc,
+        None,
+    )
+    .expect("pegged ask at 100");
+
+    book.add_limit_order(OrderId::new_uuid(), 100, 10, Side::Buy, TimeInForce::Gtc, 1, None)
+        .expect("crossing buy");
+
+    assert_eq!(book.best_ask(), None);
+    assert_eq!(book.best_bid(), None);
+}
+
+#[test]
+fn incoming_order_partially_fills_pegged_maker_and_reprices_remainder() {
+    let book = new_book();
+    book.set_reference_price(100);
+
+    book.add_oracle_pegged_order(
+        OrderId::new_uuid(),
+        Side::Sell,
+        0,
+        None,
+        20,
+        TimeInForce::Gtc,
+        None,
+    )
+    .expect("pegged ask at 100");
+
+    book.add_limit_order(OrderId::new_uuid(), 100, 5, Side::Buy, TimeInForce::Gtc, 1, None)
+        .expect("crossing buy for 5");
+
+    // 15 left resting, still pegged to the same reference.
+    assert_eq!(book.best_ask(), Some(100));
+    book.set_reference_price(110);
+    assert_eq!(book.best_ask(), Some(110));
+}
+
+#[test]
+fn fixed_and_pegged_tie_break_prefers_fixed_at_equal_price() {
+    let book = new_book();
+    book.set_reference_price(100);
+
+    let fixed_id = OrderId::new_uuid();
+    book.add_limit_order(fixed_id, 100, 5, Side::Sell, TimeInForce::Gtc, 1, None)
+        .expect("fixed ask at 100");
+    book.add_oracle_pegged_order(
+        OrderId::new_uuid(),
+        Side::Sell,
+        0,
+        None,
+        5,
+        TimeInForce::Gtc,
+        None,
+    )
+    .expect("pegged ask at 100");
+
+    // Taker only crosses the fixed order's quantity; the pegged maker,
+    // at the same effective price, is left untouched.
+    book.add_limit_order(OrderId::new_uuid(), 100, 5, Side::Buy, TimeInForce::Gtc, 2, None)
+        .expect("crossing buy for 5");
+
+    assert_eq!(book.best_ask(), Some(100));
+    let result = book.cancel_all_orders();
+    assert_eq!(result.cancelled_count(), 1);
+}
+
+#[test]
+fn pegged_order_strictly_more_aggressive_than_fixed_trades_first() {
+    let book = new_book();
+    book.set_reference_price(95);
+
+    book.add_limit_order(
+        OrderId::new_uuid(),
+        100,
+        5,
+        Side::Sell,
+        TimeInForce::Gtc,
+        1,
+        None,
+    )
+    .expect("fixed ask at 100");
+    let pegged_id = OrderId::new_uuid();
+    book.add_oracle_pegged_order(pegged_id, Side::Sell, 0, None, 5, TimeInForce::Gtc, None)
+        .expect("pegged ask at 95");
+
+    book.add_limit_order(OrderId::new_uuid(), 100, 5, Side::Buy, TimeInForce::Gtc, 2, None)
+        .expect("crossing buy for 5");
+
+    // The cheaper pegged ask (95) was filled first; the fixed ask remains.
+    assert_eq!(book.best_ask(), Some(100));
+}
+
+// ---------------------------------------------------------------------------
+// Mass cancellation includes pegged orders
+// ---------------------------------------------------------------------------
+
+#[test]
+fn cancel_all_orders_includes_pegged_orders() {
+    let book = new_book();
+    book.set_reference_price(100);
+
+    book.add_oracle_pegged_order(
+        OrderId::new_uuid(),
+        Side::Buy,
+        0,
+        None,
+        10,
+        TimeInForce::Gtc,
+        None,
+    )
+    .expect("pegged buy");
+    book.add_limit_order(OrderId::new_uuid(), 200, 10, Side::Sell, TimeInForce::Gtc, 1, None)
+        .expect("fixed ask");
+
+    let result = book.cancel_all_orders();
+    assert_eq!(result.cancelled_count(), 2);
+    assert_eq!(book.best_bid(), None);
+    assert_eq!(book.best_ask(), None);
+}
+
+#[test]
+fn cancel_orders_by_side_includes_pegged_orders() {
+    let book = new_book();
+    book.set_reference_price(100);
+
+    book.add_oracle_pegged_order(
+        OrderId::new_uuid(),
+        Side::Buy,
+        0,
+        None,
+        10,
+        TimeInForce::Gtc,
+        None,
+    )
+    .expect("pegged buy");
+    book.add_limit_order(OrderId::new_uuid(), 200, 10, Side::Sell, TimeInForce::Gtc, 1, None)
+        .expect("fixed ask");
+
+    let result: MassCancelResult = book.cancel_orders_by_side(Side::Buy);
+    assert_eq!(result.cancelled_count(), 1);
+    assert_eq!(book.best_bid(), None);
+    assert_eq!(book.best_ask(), Some(200));
+}